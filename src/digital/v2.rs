@@ -2,11 +2,64 @@
 //!
 //! Version 2 / fallible traits. Infallible implementations should set Error to `!`.
 
-/// Single digital push-pull output pin
-pub trait OutputPin {
+/// A trait that defines the error type for digital pin implementations.
+///
+/// Pulling this out of the individual pin traits means a single pin type only has to
+/// pick one `Error` for all of the digital traits it implements (`OutputPin`,
+/// `InputPin`, etc.), rather than repeating an associated `type Error` on each one.
+pub trait ErrorType {
     /// Error type
-    type Error;
+    type Error: Error;
+}
+
+/// Error trait for digital pin implementations.
+///
+/// This trait allows generic code to inspect and react to errors in a portable
+/// way, even when the concrete error type of the underlying implementation is
+/// otherwise opaque.
+pub trait Error: core::fmt::Debug {
+    /// Convert this error into a generic, portable [`ErrorKind`].
+    ///
+    /// Implementations should map as closely as possible to the actual error, and
+    /// fall back to [`ErrorKind::Other`] if conversion is not possible.
+    fn kind(&self) -> ErrorKind;
+}
 
+/// A generic digital pin error kind.
+///
+/// This represents a common set of pin-level errors. HAL implementations are
+/// free to define more specific or additional error types, but they must
+/// be able to report one of these kinds through [`Error::kind`].
+///
+/// This is not intended to be an exhaustive list of errors, and more variants
+/// may be added in the future. Since this is a non-exhaustive enum, matching
+/// on it requires a wildcard arm.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A different error occurred. The original error may contain more information.
+    Other,
+}
+
+impl Error for core::convert::Infallible {
+    fn kind(&self) -> ErrorKind {
+        match *self {}
+    }
+}
+
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Other => write!(
+                f,
+                "A different error occurred. The original error may contain more information"
+            ),
+        }
+    }
+}
+
+/// Single digital push-pull output pin
+pub trait OutputPin: ErrorType {
     /// Drives the pin low
     ///
     /// *NOTE* the actual electrical state of the pin may not actually be low, e.g. due to external
@@ -18,13 +71,33 @@ pub trait OutputPin {
     /// *NOTE* the actual electrical state of the pin may not actually be high, e.g. due to external
     /// electrical sources
     fn set_high(&mut self) -> Result<(), Self::Error>;
+
+    /// Drives the pin high or low depending on the provided value
+    ///
+    /// *NOTE* the actual electrical state of the pin may not actually be the requested state,
+    /// e.g. due to external electrical sources
+    fn set_state(&mut self, state: PinState) -> Result<(), Self::Error> {
+        match state {
+            PinState::Low => self.set_low(),
+            PinState::High => self.set_high(),
+        }
+    }
+}
+
+/// Digital output pin state
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PinState {
+    /// Low pin state
+    Low,
+    /// High pin state
+    High,
 }
 
 /// Push-pull output pin that can read its output state
 ///
 /// *This trait is available if embedded-hal is built with the `"unproven"` feature.*
 #[cfg(feature = "unproven")]
-pub trait StatefulOutputPin : OutputPin {
+pub trait StatefulOutputPin: OutputPin {
     /// Is the pin in drive high mode?
     ///
     /// *NOTE* this does *not* read the electrical state of the pin
@@ -34,21 +107,70 @@ pub trait StatefulOutputPin : OutputPin {
     ///
     /// *NOTE* this does *not* read the electrical state of the pin
     fn is_set_low(&self) -> Result<bool, Self::Error>;
+
+    /// Toggle pin output
+    ///
+    /// *NOTE* the default implementation reads back the drive state via
+    /// [`is_set_low`](StatefulOutputPin::is_set_low) and writes the opposite one; hardware that
+    /// can flip its output with a single register write should override it.
+    ///
+    /// ```
+    /// use embedded_hal::digital::v2::{ErrorType, OutputPin, StatefulOutputPin};
+    /// use std::convert::Infallible;
+    ///
+    /// /// A virtual output pin that exists purely in software
+    /// struct MyPin {
+    ///     state: bool
+    /// }
+    ///
+    /// impl ErrorType for MyPin {
+    ///    type Error = Infallible;
+    /// }
+    ///
+    /// impl OutputPin for MyPin {
+    ///    fn set_low(&mut self) -> Result<(), Self::Error> {
+    ///        self.state = false;
+    ///        Ok(())
+    ///    }
+    ///    fn set_high(&mut self) -> Result<(), Self::Error> {
+    ///        self.state = true;
+    ///        Ok(())
+    ///    }
+    /// }
+    ///
+    /// impl StatefulOutputPin for MyPin {
+    ///    fn is_set_low(&self) -> Result<bool, Self::Error> {
+    ///        Ok(!self.state)
+    ///    }
+    ///    fn is_set_high(&self) -> Result<bool, Self::Error> {
+    ///        Ok(self.state)
+    ///    }
+    /// }
+    ///
+    /// let mut pin = MyPin { state: false };
+    /// pin.toggle().unwrap();
+    /// assert!(pin.is_set_high().unwrap());
+    /// pin.toggle().unwrap();
+    /// assert!(pin.is_set_low().unwrap());
+    /// ```
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        if self.is_set_low()? {
+            self.set_high()
+        } else {
+            self.set_low()
+        }
+    }
 }
 
 /// Output pin that can be toggled
 ///
 /// *This trait is available if embedded-hal is built with the `"unproven"` feature.*
-///
-/// See [toggleable](toggleable) to use a software implementation if
-/// both [OutputPin](trait.OutputPin.html) and
-/// [StatefulOutputPin](trait.StatefulOutputPin.html) are
-/// implemented. Otherwise, implement this using hardware mechanisms.
 #[cfg(feature = "unproven")]
-pub trait ToggleableOutputPin {
-    /// Error type
-    type Error;
-
+#[deprecated(
+    since = "0.2.4",
+    note = "use `StatefulOutputPin::toggle` instead, which is implemented for any pin that is both an `OutputPin` and a `StatefulOutputPin`"
+)]
+pub trait ToggleableOutputPin: ErrorType {
     /// Toggle pin output.
     fn toggle(&mut self) -> Result<(), Self::Error>;
 }
@@ -56,48 +178,14 @@ pub trait ToggleableOutputPin {
 /// If you can read **and** write the output state, a pin is
 /// toggleable by software.
 ///
-/// ```
-/// use embedded_hal::digital::v2::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
-/// use embedded_hal::digital::v2::toggleable;
-/// use std::convert::Infallible;
-///
-/// /// A virtual output pin that exists purely in software
-/// struct MyPin {
-///     state: bool
-/// }
-///
-/// impl OutputPin for MyPin {
-///    type Error = Infallible;
-///
-///    fn set_low(&mut self) -> Result<(), Self::Error> {
-///        self.state = false;
-///        Ok(())
-///    }
-///    fn set_high(&mut self) -> Result<(), Self::Error> {
-///        self.state = true;
-///        Ok(())
-///    }
-/// }
-///
-/// impl StatefulOutputPin for MyPin {
-///    fn is_set_low(&self) -> Result<bool, Self::Error> {
-///        Ok(!self.state)
-///    }
-///    fn is_set_high(&self) -> Result<bool, Self::Error> {
-///        Ok(self.state)
-///    }
-/// }
-///
-/// /// Opt-in to the software implementation.
-/// impl toggleable::Default for MyPin {}
-///
-/// let mut pin = MyPin { state: false };
-/// pin.toggle().unwrap();
-/// assert!(pin.is_set_high().unwrap());
-/// pin.toggle().unwrap();
-/// assert!(pin.is_set_low().unwrap());
-/// ```
+/// *This module is deprecated: [`StatefulOutputPin::toggle`] now provides the same
+/// software-driven `toggle()` directly, with no opt-in marker trait required.*
 #[cfg(feature = "unproven")]
+#[deprecated(
+    since = "0.2.4",
+    note = "use `StatefulOutputPin::toggle` instead, which is implemented for any pin that is both an `OutputPin` and a `StatefulOutputPin`"
+)]
+#[allow(deprecated)]
 pub mod toggleable {
     use super::{OutputPin, StatefulOutputPin, ToggleableOutputPin};
 
@@ -110,8 +198,6 @@ pub mod toggleable {
     where
         P: Default,
     {
-        type Error = P::Error;
-
         /// Toggle pin output
         fn toggle(&mut self) -> Result<(), Self::Error> {
             if self.is_set_low()? {
@@ -124,13 +210,43 @@ pub mod toggleable {
 }
 
 /// Single digital input pin
-pub trait InputPin {
-    /// Error type
-    type Error;
-
+pub trait InputPin: ErrorType {
     /// Is the input pin high?
     fn is_high(&self) -> Result<bool, Self::Error>;
 
     /// Is the input pin low?
     fn is_low(&self) -> Result<bool, Self::Error>;
 }
+
+/// Wait for a digital input pin to reach a given level, or to see an edge
+///
+/// *This trait is available if embedded-hal is built with the `"wait"` feature.*
+///
+/// # Details
+///
+/// Calling `wait_for_high` or `wait_for_low` returns immediately if the pin is already in the
+/// requested state when the method is called, rather than waiting for the *next* transition into
+/// that state; a caller that needs to observe a transition should use one of the edge methods
+/// instead. Missing this distinction is the classic source of missed-level races, where a level
+/// that was asserted just before the wait call would otherwise be lost.
+///
+/// This trait may be implemented by blocking drivers (parking the calling thread, or polling
+/// until the hardware's interrupt flag is observed) as well as, in the future, `async` executors
+/// built on top of the same pin-level and edge primitives.
+#[cfg(feature = "wait")]
+pub trait Wait: ErrorType {
+    /// Wait until the pin is high. If it is already high, return immediately.
+    fn wait_for_high(&mut self) -> Result<(), Self::Error>;
+
+    /// Wait until the pin is low. If it is already low, return immediately.
+    fn wait_for_low(&mut self) -> Result<(), Self::Error>;
+
+    /// Wait for the pin to undergo a transition from low to high.
+    fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error>;
+
+    /// Wait for the pin to undergo a transition from high to low.
+    fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error>;
+
+    /// Wait for the pin to undergo any transition, i.e. low to high OR high to low.
+    fn wait_for_any_edge(&mut self) -> Result<(), Self::Error>;
+}